@@ -0,0 +1,111 @@
+use crate::shared::int::*;
+
+use core::simd::*;
+
+/// Two decimal digits for every value in `0..100`, used to fill two output bytes at a time
+/// instead of dividing by 10 one digit at a time.
+static DIGIT_PAIRS: [[u8; 2]; 100] = {
+    let mut pairs = [[0u8; 2]; 100];
+    let mut i = 0;
+    while i < 100 {
+        pairs[i] = [b'0' + (i / 10) as u8, b'0' + (i % 10) as u8];
+        i += 1;
+    }
+    pairs
+};
+
+macro_rules! decimal_impl {
+    ($u:ty) => {
+        impl<const LANES: usize> Simd<$u, LANES>
+        where
+            LaneCount<LANES>: SupportedLaneCount,
+        {
+            /// Returns the number of decimal digits needed to print each lane (`1` for a lane
+            /// that is `0`), computed branchlessly via [`FastExactInt::ilog_const_base`] instead
+            /// of the usual repeated-division probing.
+            #[inline(always)]
+            pub fn decimal_len(self) -> Self {
+                // `ilog_const_base` asserts every lane is nonzero, so route zero lanes through
+                // `1` (an arbitrary nonzero placeholder) and fix their digit count up after.
+                let is_zero = self.simd_eq(Simd::splat(0));
+                let safe = is_zero.select(Simd::splat(1), self);
+                let digits = safe.ilog_const_base::<10>() + Simd::splat(1);
+
+                is_zero.select(Simd::splat(1), digits)
+            }
+
+            /// Writes the decimal digits of each lane into `out` (`"0"` for a lane that is `0`),
+            /// one lane's digits per contiguous slice of `out` starting at the corresponding
+            /// entry of `offsets`, and advances each offset past what it wrote.
+            ///
+            /// `out` must be large enough to hold every lane's digits at its offset, and
+            /// `offsets` must have at least `LANES` entries.
+            #[inline(always)]
+            pub fn write_decimal(self, out: &mut [u8], offsets: &mut [usize]) {
+                let lens = self.decimal_len();
+
+                for lane in 0..LANES {
+                    let len = lens[lane] as usize;
+                    let mut value = self[lane];
+                    let start = offsets[lane];
+                    let mut pos = start + len;
+
+                    while value >= 100 {
+                        pos -= 2;
+                        let pair = DIGIT_PAIRS[(value % 100) as usize];
+                        out[pos..pos + 2].copy_from_slice(&pair);
+                        value /= 100;
+                    }
+
+                    if value >= 10 {
+                        pos -= 2;
+                        let pair = DIGIT_PAIRS[value as usize];
+                        out[pos..pos + 2].copy_from_slice(&pair);
+                    } else {
+                        pos -= 1;
+                        out[pos] = b'0' + value as u8;
+                    }
+
+                    offsets[lane] = start + len;
+                }
+            }
+        }
+    };
+}
+
+decimal_impl!(u32);
+decimal_impl!(u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_len_treats_zero_as_one_digit() {
+        let v = Simd::<u32, 4>::from_array([0, 7, 100, 65536]);
+        assert_eq!(v.decimal_len(), Simd::from_array([1, 1, 3, 5]));
+    }
+
+    #[test]
+    fn write_decimal_handles_a_zero_lane() {
+        let v = Simd::<u32, 4>::from_array([0, 7, 100, 65536]);
+
+        let mut out = [0u8; 10];
+        let mut offsets = [0usize, 1, 2, 5];
+        v.write_decimal(&mut out, &mut offsets);
+
+        assert_eq!(&out[0..1], b"0");
+        assert_eq!(&out[1..2], b"7");
+        assert_eq!(&out[2..5], b"100");
+        assert_eq!(&out[5..10], b"65536");
+    }
+
+    #[test]
+    fn decimal_len_handles_digit_count_boundaries() {
+        let v = Simd::<u32, 8>::from_array([9, 10, 99, 100, 999, 1000, u32::MAX - 1, u32::MAX]);
+        assert_eq!(
+            v.decimal_len(),
+            Simd::from_array([1, 2, 2, 3, 3, 4, 10, 10])
+        );
+    }
+}
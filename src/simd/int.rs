@@ -5,7 +5,7 @@ use mem::size_of;
 use std::mem;
 
 macro_rules! unsigned_impl {
-    ($u:ty,$s:ty,$f:ty,$mant_bits:expr) => {
+    ($u:ty,$s:ty,$f:ty,$mant_bits:expr,$wide:ty) => {
         impl<const LANES: usize> FastApproxInt for Simd<$u, LANES>
         where
             LaneCount<LANES>: SupportedLaneCount,
@@ -64,34 +64,445 @@ macro_rules! unsigned_impl {
                         + min_digits.ipow_const_coeff::<BASE>().simd_gt(self).to_int())
                     .cast::<$u>()
                 } else {
-                    // this if statement avoids potential horrible codegen
-                    let max_signed: $u = if BASE as $u > <$s>::MAX as $u {
-                        0
-                    } else {
-                        <$s>::MAX.ilog(BASE as $s) as $u
-                    };
-                    let max_unsigned: $u = <$u>::MAX.ilog(BASE as $u) as $u;
-
-                    let x_signed = self.cast::<$s>();
-
-                    // if the input is greater than i32 max, we can use the last bit to determine if we should account
-                    // for the incorrect comparisons in the first loop
-                    let mut result = (x_signed >> Simd::splat((<$u>::BITS - 1) as $s)).cast::<$u>()
-                        & Simd::splat(max_signed);
-
-                    for i in 1..=max_signed as u32 {
-                        // if the input is greater than i32 max, these will all result in 0s
-                        result -= x_signed
-                            .simd_ge(Simd::splat(BASE.pow(i) as $s))
-                            .to_int()
-                            .cast::<$u>();
+                    // instead of dividing the input down through up to `max_unsigned` compares
+                    // against precomputed `BASE.pow(i)` constants, multiply a per-lane threshold
+                    // up by `BASE` each round and stop as soon as no lane can advance further.
+                    let max_unsigned = <$u>::MAX.ilog(BASE as $u);
+
+                    let mut result = Simd::splat(0 as $u);
+                    let mut threshold = Simd::splat(1 as $u);
+                    let mut active = Mask::<$s, LANES>::splat(true);
+
+                    for _ in 0..max_unsigned {
+                        if !active.any() {
+                            break;
+                        }
+
+                        // a lane can't advance further once multiplying its threshold by BASE
+                        // would overflow, regardless of what self.simd_ge would otherwise say
+                        let no_overflow = threshold.simd_le(Simd::splat(<$u>::MAX / (BASE as $u)));
+                        let next_threshold = threshold * Simd::splat(BASE as $u);
+                        let advances = active & no_overflow & self.simd_ge(next_threshold);
+
+                        result = advances.select(result + Simd::splat(1), result);
+                        threshold = advances.select(next_threshold, threshold);
+                        active = advances;
+                    }
+
+                    result
+                }
+            }
+
+            /// Computes the base-`base` logarithm of each lane, using a base that is only
+            /// known at runtime rather than a const generic.
+            ///
+            /// Mirrors [`FastExactInt::ilog_const_base`], but moves the multiplier/coefficient
+            /// computation in [`get_multiplier`] to runtime so callers that don't know `base`
+            /// at compile time can still use the fast float-exponent path.
+            #[inline(always)]
+            fn ilog_base(self, base: $u) -> Self {
+                assert!(!self.simd_le(Simd::splat(0)).any(), "invalid input: 0");
+                unsafe { self.ilog_base_unchecked(base) }
+            }
+
+            /// # Safety
+            ///
+            /// Every lane of `self` must be nonzero.
+            #[inline(always)]
+            unsafe fn ilog_base_unchecked(self, base: $u) -> Self {
+                assert!(
+                    base != 0 && base != 1 && base as u64 <= <$u>::MAX as u64,
+                    "invalid base: {:?}",
+                    base
+                );
+
+                // base 2 already has an exact (non-approximated) path
+                if base == 2 {
+                    return self.ilog_const_base_unchecked::<2>();
+                }
+
+                let numerator: $u = (<$u>::MAX / (<$u>::MAX.ilog2() as $u + 1)) + 1;
+                let shift: $u = numerator.ilog2() as $u;
+                // `get_multiplier` takes a `u32` base; a clamped base only loosens the estimate,
+                // which the correction loop below walks back to the exact result
+                let multiplier: $u =
+                    get_multiplier(numerator as u64, base.min(u32::MAX as $u) as u32) as $u;
+
+                let k = self.ilog_const_base_unchecked::<2>();
+
+                // unlike the const-generic `BASE <= 7` path, `base` here is unbounded, so the
+                // `(k + 1) * multiplier` product can overflow `$u` long before the final shift
+                // brings it back into range. `$wide` isn't always a supported SIMD lane type
+                // (`u128` for `u64` lanes isn't), so widen per-lane the same way
+                // `log2_fixed_scalar` does for its squaring step.
+                let k_arr = k.to_array();
+                let mut min_digits_arr = [0 as $u; LANES];
+                for lane in 0..LANES {
+                    min_digits_arr[lane] = (((k_arr[lane] as $wide + 1) * (multiplier as $wide))
+                        >> (shift as u32)) as $u;
+                }
+                let min_digits = Simd::from_array(min_digits_arr);
+
+                // a widened product keeps the approximation close, but overflow elsewhere (or a
+                // base far from a power of two) can still leave it off by more than one, so walk
+                // the result to the exact digit count instead of trusting a single +-1 step.
+                // `ipow_base` wraps on overflow, and a wrapped power compares against `self`
+                // arbitrarily; a saturated one can't be told apart from a real `$u::MAX`. Use
+                // the checked variant instead, so an overflowing power always reads as bigger
+                // than `self`.
+                let mut result = min_digits;
+                for _ in 0..<$u>::BITS {
+                    let (power, power_overflow) = result.ipow_base_checked(base);
+                    let too_high =
+                        result.simd_gt(Simd::splat(0)) & (power_overflow | power.simd_gt(self));
+                    let (next_power, next_overflow) =
+                        (result + Simd::splat(1)).ipow_base_checked(base);
+                    let too_low = !next_overflow & next_power.simd_le(self);
+
+                    if !(too_high | too_low).any() {
+                        break;
+                    }
+
+                    result = too_high.select(result - Simd::splat(1), result);
+                    result = too_low.select(result + Simd::splat(1), result);
+                }
+
+                result
+            }
+
+            /// Raises `base` to the power of each lane of `self` (treated as the exponent),
+            /// computed via square-and-multiply over the bits of the exponent. This is the
+            /// runtime-base counterpart to [`FastExactInt::ipow_const_coeff`]; [`ilog_base`]
+            /// verifies its results with [`ipow_base_checked`](Self::ipow_base_checked).
+            ///
+            /// Wraps on overflow like the const-coefficient version; see
+            /// [`ipow_base_checked`](Self::ipow_base_checked) for a variant that reports it.
+            #[inline(always)]
+            fn ipow_base(self, base: $u) -> Self {
+                let mut exponent = self;
+                let mut base_power = Simd::splat(base);
+                let mut result = Simd::splat(1 as $u);
+
+                while exponent.simd_gt(Simd::splat(0)).any() {
+                    result = (exponent & Simd::splat(1))
+                        .simd_eq(Simd::splat(1))
+                        .select(result * base_power, result);
+                    base_power *= base_power;
+                    exponent >>= Simd::splat(1);
+                }
+
+                result
+            }
+
+            /// Like [`ipow_base`](Self::ipow_base), but instead of silently wrapping, returns a
+            /// mask of which lanes overflowed `$u::MAX` alongside the (possibly wrapped) result.
+            ///
+            /// Unlike [`ipow_const_coeff_checked`](Self::ipow_const_coeff_checked), `base_power`
+            /// here is squared at runtime rather than precomputed per bit, so a lane can also
+            /// overflow indirectly: once `base_power` itself overflows, every lane with exponent
+            /// bits still unconsumed would multiply by that corrupted value, so all of them are
+            /// flagged too.
+            #[inline(always)]
+            fn ipow_base_checked(self, base: $u) -> (Self, Mask<$s, LANES>) {
+                let mut exponent = self;
+                let mut base_power = Simd::splat(base);
+                let mut result = Simd::splat(1 as $u);
+                let mut overflow = Mask::splat(false);
+
+                while exponent.simd_gt(Simd::splat(0)).any() {
+                    let bit_set = (exponent & Simd::splat(1)).simd_eq(Simd::splat(1));
+                    // `base_power` wraps to 0 once a squaring overflows; every lane that can
+                    // still use it is already flagged by then, so divide by 1 instead of 0
+                    let divisor = base_power.simd_max(Simd::splat(1));
+
+                    // a lane overflows here if multiplying its running result by the current
+                    // `base_power` would exceed `$u::MAX`
+                    overflow |= bit_set
+                        & base_power.simd_gt(Simd::splat(1))
+                        & result.simd_gt(Simd::splat(<$u>::MAX) / divisor);
+                    result = bit_set.select(result * base_power, result);
+
+                    exponent >>= Simd::splat(1);
+                    if !exponent.simd_gt(Simd::splat(0)).any() {
+                        break;
+                    }
+
+                    // every lane that still has exponent bits left to consume will multiply by
+                    // the next squaring of `base_power`, so if that squaring itself overflows,
+                    // all of them are tainted even before their own bit comes up
+                    overflow |= exponent.simd_gt(Simd::splat(0))
+                        & base_power.simd_gt(Simd::splat(1))
+                        & base_power.simd_gt(Simd::splat(<$u>::MAX) / divisor);
+                    base_power *= base_power;
+                }
+
+                (result, overflow)
+            }
+
+            /// Like [`ipow_base`](Self::ipow_base), but clamps overflowing lanes to `$u::MAX`
+            /// instead of wrapping.
+            #[inline(always)]
+            fn ipow_base_saturating(self, base: $u) -> Self {
+                let (result, overflow) = self.ipow_base_checked(base);
+                overflow.select(Simd::splat(<$u>::MAX), result)
+            }
+
+            #[inline(always)]
+            fn ipow_const_coeff<const COEFF: u32>(self) -> Self {
+                assert!(
+                    COEFF <= <$u>::MAX as u32,
+                    "invalid coefficient: {:?}",
+                    COEFF
+                );
+
+                match COEFF {
+                    0 => self
+                        .simd_eq(Simd::splat(0))
+                        .select(Simd::splat(1), Simd::splat(0)),
+                    1 => Simd::splat(1),
+                    2 => Simd::splat(2) << self,
+                    _ => {
+                        let bit_count = <$u>::MAX.ilog(COEFF as $u).next_power_of_two().ilog2();
+
+                        let mut bit = 0b1;
+                        let mut result = Simd::splat(1);
+                        // calculate the power at each bit and multiply with the previous value
+                        for _i in 0..bit_count {
+                            result *= (self & Simd::splat(bit))
+                                .simd_eq(Simd::splat(bit))
+                                .select(Simd::splat(COEFF.pow(bit as u32) as $u), Simd::splat(1));
+                            bit <<= 1;
+                        }
+
+                        result
+                    }
+                }
+            }
+
+            /// Like [`ipow_const_coeff`](Self::ipow_const_coeff), but instead of silently
+            /// wrapping, returns a mask of which lanes overflowed `$u::MAX` alongside the
+            /// (possibly wrapped) result.
+            #[inline(always)]
+            fn ipow_const_coeff_checked<const COEFF: u32>(self) -> (Self, Mask<$s, LANES>) {
+                assert!(
+                    COEFF <= <$u>::MAX as u32,
+                    "invalid coefficient: {:?}",
+                    COEFF
+                );
+
+                match COEFF {
+                    0 => (
+                        self.simd_eq(Simd::splat(0))
+                            .select(Simd::splat(1), Simd::splat(0)),
+                        Mask::splat(false),
+                    ),
+                    1 => (Simd::splat(1), Mask::splat(false)),
+                    2 => {
+                        let overflow = self.simd_ge(Simd::splat(<$u>::BITS as $u - 1));
+                        let safe_shift = self.simd_min(Simd::splat(<$u>::BITS as $u - 1));
+                        (Simd::splat(2) << safe_shift, overflow)
                     }
+                    _ => {
+                        // enough bits to spell every exponent up to `ilog(MAX, COEFF)`, so any
+                        // exponent with a bit above them overflows
+                        let bit_count = <$u>::MAX.ilog(COEFF as $u).ilog2() + 1;
 
-                    for i in (max_signed + 1) as u32..=max_unsigned as u32 {
-                        result -= self
-                            .simd_ge(Simd::splat(BASE.pow(i) as $u))
-                            .to_int()
-                            .cast::<$u>();
+                        let mut bit = 0b1;
+                        let mut result = Simd::splat(1);
+                        let mut overflow = (self >> Simd::splat(bit_count as $u))
+                            .simd_ne(Simd::splat(0));
+                        for _i in 0..bit_count {
+                            let factor = (self & Simd::splat(bit))
+                                .simd_eq(Simd::splat(bit))
+                                .select(Simd::splat((COEFF as $u).pow(bit as u32)), Simd::splat(1));
+
+                            // a lane overflows this step if its running result is already too
+                            // large to multiply by `factor` without exceeding `$u::MAX`
+                            overflow |= factor.simd_gt(Simd::splat(1))
+                                & result.simd_gt(Simd::splat(<$u>::MAX) / factor);
+
+                            result *= factor;
+                            bit <<= 1;
+                        }
+
+                        (result, overflow)
+                    }
+                }
+            }
+
+            /// Like [`ipow_const_coeff`](Self::ipow_const_coeff), but clamps overflowing lanes
+            /// to `$u::MAX` instead of wrapping.
+            #[inline(always)]
+            fn ipow_const_coeff_saturating<const COEFF: u32>(self) -> Self {
+                let (result, overflow) = self.ipow_const_coeff_checked::<COEFF>();
+                overflow.select(Simd::splat(<$u>::MAX), result)
+            }
+        }
+
+        impl<const LANES: usize> Simd<$u, LANES>
+        where
+            LaneCount<LANES>: SupportedLaneCount,
+        {
+            /// Computes `log2(self)` as a Q(`FRAC_BITS`) fixed-point number, per lane.
+            ///
+            /// This is the same iterative-squaring kernel [`get_multiplier`] uses internally
+            /// to precompute its constant, promoted to a method so callers can get a fractional
+            /// logarithm instead of just the integer floor. The integer part comes from the
+            /// existing [`FastExactInt::ilog_const_base_unchecked::<2>`] path; the squaring loop
+            /// then fills in `FRAC_BITS` fractional bits below it.
+            #[inline(always)]
+            pub fn log2_fixed<const FRAC_BITS: u32>(self) -> Self {
+                assert!(!self.simd_le(Simd::splat(0)).any(), "invalid input: 0");
+                // `log2_fixed_scalar` packs its result as `(int_part << FRAC_BITS) |
+                // result_frac` in a single `$u`-width integer, so `FRAC_BITS` alone isn't
+                // enough headroom -- `int_part` can be as large as `BITS - 1`, which takes
+                // `ilog2(BITS)` bits above the fractional part, or the shift silently
+                // overflows and corrupts the integer part. The check below reserves
+                // `ilog2(BITS) + 1` bits for it.
+                assert!(
+                    FRAC_BITS + <$u>::BITS.ilog2() + 1 <= <$u>::BITS,
+                    "FRAC_BITS too large: {:?}",
+                    FRAC_BITS
+                );
+
+                let int_part = unsafe { self.ilog_const_base_unchecked::<2>() };
+                let self_arr = self.to_array();
+                let int_arr = int_part.to_array();
+
+                let mut out = [0 as $u; LANES];
+                for lane in 0..LANES {
+                    out[lane] = log2_fixed_scalar::<FRAC_BITS>(self_arr[lane], int_arr[lane]);
+                }
+
+                Simd::from_array(out)
+            }
+
+            /// Computes `log_base(self)` (for the const `BASE`) as a Q(`FRAC_BITS`) fixed-point
+            /// number, per lane, by dividing [`log2_fixed`](Self::log2_fixed) by the constant
+            /// `log2(BASE)` multiplier already produced by [`get_multiplier`] -- the same trick
+            /// `ilog_const_base_fast_approx` uses for the integer case.
+            #[inline(always)]
+            pub fn log_base_fixed<const BASE: u32, const FRAC_BITS: u32>(self) -> Self {
+                let multiplier: $u = get_multiplier(1u64 << FRAC_BITS, BASE) as $u;
+                let log2_self = self.log2_fixed::<FRAC_BITS>();
+
+                // like `ilog_base_unchecked`, this product can overflow `$u` well before the
+                // shift brings it back down, so widen it per-lane the same way
+                // `log2_fixed_scalar` widens its own squaring step.
+                let log2_arr = log2_self.to_array();
+                let mut out = [0 as $u; LANES];
+                for lane in 0..LANES {
+                    out[lane] =
+                        (((log2_arr[lane] as $wide) * (multiplier as $wide)) >> FRAC_BITS) as $u;
+                }
+
+                Simd::from_array(out)
+            }
+        }
+
+        #[inline(always)]
+        fn log2_fixed_scalar<const FRAC_BITS: u32>(value: $u, int_part: $u) -> $u {
+            if FRAC_BITS == 0 {
+                return int_part;
+            }
+
+            // normalize `value` into [1, 2) represented as Q(FRAC_BITS)
+            let shift = <$u>::BITS - 1 - int_part;
+            let frac_shift = <$u>::BITS - 1 - FRAC_BITS;
+            let mut z: $wide = ((value as $wide) << shift) >> frac_shift;
+
+            let mut result_frac: $u = 0;
+            let mut b: $u = 1 << (FRAC_BITS - 1);
+            let two_fixed: $wide = 2 << FRAC_BITS;
+
+            while b != 0 {
+                z = (z * z) >> FRAC_BITS;
+                if z >= two_fixed {
+                    z >>= 1;
+                    result_frac |= b;
+                }
+                b >>= 1;
+            }
+
+            (int_part << FRAC_BITS) | result_frac
+        }
+    };
+}
+
+unsigned_impl!(u32, i32, f32, 23, u64);
+unsigned_impl!(u64, i64, f64, 52, u128);
+
+// `u8`/`u16` don't have a same-width float to exponent-shift through like `unsigned_impl!`
+// does for `u32`/`u64`, so the BASE == 2 branch instead casts up to `f32`. Unlike the wider
+// types, that cast is always exact (an `f32` mantissa holds 24 bits, more than either type's
+// full range), so none of the clamp-and-round-correct dance in `unsigned_impl!` is needed.
+macro_rules! small_unsigned_impl {
+    ($u:ty,$s:ty) => {
+        impl<const LANES: usize> FastApproxInt for Simd<$u, LANES>
+        where
+            LaneCount<LANES>: SupportedLaneCount,
+        {
+            #[inline(always)]
+            unsafe fn ilog_const_base_fast_approx<const BASE: u32>(self) -> Self {
+                let numerator: $u = (<$u>::MAX / (<$u>::MAX.ilog2() as $u + 1)) + 1;
+                let shift: $u = numerator.ilog2() as $u;
+                let multiplier: $u = get_multiplier(numerator as u64, BASE) as $u;
+
+                ((self.ilog_const_base_unchecked::<2>() + Simd::splat(1)) * Simd::splat(multiplier))
+                    >> Simd::splat(shift)
+            }
+        }
+
+        impl<const LANES: usize> FastExactInt for Simd<$u, LANES>
+        where
+            LaneCount<LANES>: SupportedLaneCount,
+        {
+            #[inline(always)]
+            fn ilog_const_base<const BASE: u32>(self) -> Self {
+                assert!(!self.simd_le(Simd::splat(0)).any(), "invalid input: 0");
+                unsafe { self.ilog_const_base_unchecked::<BASE>() }
+            }
+
+            #[inline(always)]
+            unsafe fn ilog_const_base_unchecked<const BASE: u32>(self) -> Self {
+                if BASE == 0 || BASE == 1 || BASE as $u > <$u>::MAX {
+                    panic!("invalid base: {:?}", BASE);
+                } else if BASE == 2 {
+                    const UNSIGNED_LOG2: $u = (<$u>::BITS - 1) as $u;
+
+                    let float = self.cast::<f32>();
+                    let exponent =
+                        (float.to_bits().cast::<i32>() >> Simd::splat(23)) - Simd::splat(127);
+
+                    exponent.cast::<$u>().simd_min(Simd::splat(UNSIGNED_LOG2))
+                } else {
+                    // `$u` is narrow enough that the fast-approx-plus-(+-1)-correction trick
+                    // `unsigned_impl!` uses for `BASE <= 7` isn't safe here: the approximation
+                    // can overshoot by more than `ipow_const_coeff`'s bit budget was sized for
+                    // (e.g. `ilog_const_base::<4>()` on `u8` values 128..=255), and that overshoot
+                    // then goes undetected. Use the same robust multiply-up loop as the large-base
+                    // path for every base from here on instead -- multiply a per-lane threshold up
+                    // by `BASE` each round and stop as soon as no lane can advance further.
+                    let max_unsigned = <$u>::MAX.ilog(BASE as $u);
+
+                    let mut result = Simd::splat(0 as $u);
+                    let mut threshold = Simd::splat(1 as $u);
+                    let mut active = Mask::<$s, LANES>::splat(true);
+
+                    for _ in 0..max_unsigned {
+                        if !active.any() {
+                            break;
+                        }
+
+                        // a lane can't advance further once multiplying its threshold by BASE
+                        // would overflow, regardless of what self.simd_ge would otherwise say
+                        let no_overflow = threshold.simd_le(Simd::splat(<$u>::MAX / (BASE as $u)));
+                        let next_threshold = threshold * Simd::splat(BASE as $u);
+                        let advances = active & no_overflow & self.simd_ge(next_threshold);
+
+                        result = advances.select(result + Simd::splat(1), result);
+                        threshold = advances.select(next_threshold, threshold);
+                        active = advances;
                     }
 
                     result
@@ -129,12 +540,71 @@ macro_rules! unsigned_impl {
                     }
                 }
             }
+
+            /// Like [`ipow_const_coeff`](Self::ipow_const_coeff), but instead of silently
+            /// wrapping, returns a mask of which lanes overflowed `$u::MAX` alongside the
+            /// (possibly wrapped) result.
+            #[inline(always)]
+            fn ipow_const_coeff_checked<const COEFF: u32>(self) -> (Self, Mask<$s, LANES>) {
+                assert!(
+                    COEFF <= <$u>::MAX as u32,
+                    "invalid coefficient: {:?}",
+                    COEFF
+                );
+
+                match COEFF {
+                    0 => (
+                        self.simd_eq(Simd::splat(0))
+                            .select(Simd::splat(1), Simd::splat(0)),
+                        Mask::splat(false),
+                    ),
+                    1 => (Simd::splat(1), Mask::splat(false)),
+                    2 => {
+                        let overflow = self.simd_ge(Simd::splat(<$u>::BITS as $u - 1));
+                        let safe_shift = self.simd_min(Simd::splat(<$u>::BITS as $u - 1));
+                        (Simd::splat(2) << safe_shift, overflow)
+                    }
+                    _ => {
+                        // enough bits to spell every exponent up to `ilog(MAX, COEFF)`, so any
+                        // exponent with a bit above them overflows
+                        let bit_count = <$u>::MAX.ilog(COEFF as $u).ilog2() + 1;
+
+                        let mut bit = 0b1;
+                        let mut result = Simd::splat(1);
+                        let mut overflow = (self >> Simd::splat(bit_count as $u))
+                            .simd_ne(Simd::splat(0));
+                        for _i in 0..bit_count {
+                            let factor = (self & Simd::splat(bit))
+                                .simd_eq(Simd::splat(bit))
+                                .select(Simd::splat((COEFF as $u).pow(bit as u32)), Simd::splat(1));
+
+                            // a lane overflows this step if its running result is already too
+                            // large to multiply by `factor` without exceeding `$u::MAX`
+                            overflow |= factor.simd_gt(Simd::splat(1))
+                                & result.simd_gt(Simd::splat(<$u>::MAX) / factor);
+
+                            result *= factor;
+                            bit <<= 1;
+                        }
+
+                        (result, overflow)
+                    }
+                }
+            }
+
+            /// Like [`ipow_const_coeff`](Self::ipow_const_coeff), but clamps overflowing lanes
+            /// to `$u::MAX` instead of wrapping.
+            #[inline(always)]
+            fn ipow_const_coeff_saturating<const COEFF: u32>(self) -> Self {
+                let (result, overflow) = self.ipow_const_coeff_checked::<COEFF>();
+                overflow.select(Simd::splat(<$u>::MAX), result)
+            }
         }
     };
 }
 
-unsigned_impl!(u32, i32, f32, 23);
-unsigned_impl!(u64, i64, f64, 52);
+small_unsigned_impl!(u8, i8);
+small_unsigned_impl!(u16, i16);
 
 // Adapted from here:
 // https://github.com/dmoulding/log2fix/blob/8955391773b666c12c03dfbdfa9707e298a42ae1/log2fix.c#L9
@@ -166,3 +636,113 @@ pub(crate) const fn get_multiplier(numerator: u64, base: u32) -> u64 {
 
     (((numerator as u128) << PRECISION) / (result as u128)) as u64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ilog_base_matches_std_ilog_for_large_u32() {
+        let x = Simd::<u32, 4>::splat(3_000_000_000);
+
+        assert_eq!(x.ilog_base(2), Simd::splat(3_000_000_000u32.ilog(2)));
+        assert_eq!(x.ilog_base(4), Simd::splat(3_000_000_000u32.ilog(4)));
+        assert_eq!(x.ilog_base(16), Simd::splat(3_000_000_000u32.ilog(16)));
+        assert_eq!(
+            x.ilog_base(65536),
+            Simd::splat(3_000_000_000u32.ilog(65536))
+        );
+    }
+
+    #[test]
+    fn ilog_base_matches_std_ilog_across_random_u32_samples() {
+        // a small deterministic LCG instead of a `rand` dependency -- just needs to cover the
+        // upper half of `u32` where the unwidened multiplier used to wrap.
+        let mut state: u32 = 0x1234_5678;
+        for base in [2u32, 4, 16, 65536] {
+            for _ in 0..256 {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let x = (state | (1 << 31)).max(1);
+
+                assert_eq!(
+                    Simd::<u32, 1>::splat(x).ilog_base(base)[0],
+                    x.ilog(base),
+                    "x={x}, base={base}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn log_base_fixed_matches_float_log_for_large_u32() {
+        let x = Simd::<u32, 4>::splat(4_000_000_000);
+        let fixed = x.log_base_fixed::<2, 16>();
+        let expected = (4_000_000_000f64.log2() * (1u64 << 16) as f64).round() as u32;
+
+        for lane in fixed.to_array() {
+            assert!(
+                lane.abs_diff(expected) <= 64,
+                "fixed={lane}, expected={expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn ilog_base_matches_std_ilog_at_the_top_of_the_digit_band() {
+        // `base^result` overflows `u32` right around here for these bases, which is exactly
+        // where `ipow_base`'s old wrapping comparisons broke the correction loop's convergence.
+        assert_eq!(
+            Simd::<u32, 1>::splat(u32::MAX).ilog_base(3)[0],
+            u32::MAX.ilog(3)
+        );
+        assert_eq!(
+            Simd::<u32, 1>::splat(3_000_000_000).ilog_base(4)[0],
+            3_000_000_000u32.ilog(4)
+        );
+    }
+
+    #[test]
+    fn log_base_fixed_does_not_corrupt_int_part_for_large_frac_bits() {
+        let x = Simd::<u32, 1>::splat(16_777_224);
+        let fixed = x.log_base_fixed::<10, 26>()[0];
+        let expected = (16_777_224f64.log10() * (1u64 << 26) as f64).round() as u32;
+
+        assert!(
+            fixed.abs_diff(expected) <= 1 << 10,
+            "fixed={fixed}, expected={expected}"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "FRAC_BITS too large")]
+    fn log2_fixed_rejects_frac_bits_that_would_not_leave_room_for_int_part() {
+        // `FRAC_BITS = 30` used to run (and silently corrupt the integer part for large
+        // inputs) because the old guard only checked `FRAC_BITS < BITS`.
+        Simd::<u32, 1>::splat(16_777_216).log2_fixed::<30>();
+    }
+
+    #[test]
+    fn ilog_const_base_matches_std_ilog_for_all_u8_values() {
+        for value in 1..=u8::MAX {
+            let simd = Simd::<u8, 1>::splat(value);
+
+            assert_eq!(simd.ilog_const_base::<2>()[0], value.ilog(2) as u8);
+            assert_eq!(simd.ilog_const_base::<3>()[0], value.ilog(3) as u8);
+            assert_eq!(simd.ilog_const_base::<4>()[0], value.ilog(4) as u8);
+            assert_eq!(simd.ilog_const_base::<5>()[0], value.ilog(5) as u8);
+            assert_eq!(simd.ilog_const_base::<6>()[0], value.ilog(6) as u8);
+            assert_eq!(simd.ilog_const_base::<7>()[0], value.ilog(7) as u8);
+        }
+    }
+
+    #[test]
+    fn ilog_const_base_matches_std_ilog_for_boundary_u16_values() {
+        for value in [1u16, 2, 255, 256, 65534, 65535] {
+            let simd = Simd::<u16, 1>::splat(value);
+
+            assert_eq!(simd.ilog_const_base::<2>()[0], value.ilog(2) as u16);
+            assert_eq!(simd.ilog_const_base::<4>()[0], value.ilog(4) as u16);
+            assert_eq!(simd.ilog_const_base::<7>()[0], value.ilog(7) as u16);
+        }
+    }
+}